@@ -0,0 +1,70 @@
+// Shared AES-256-GCM helpers so every "encrypt before it leaves the
+// process" backend (CryptoStore, S3Store, ...) seals bytes the same way
+// instead of reimplementing nonce handling per backend.
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+pub const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+pub fn cipher_from_key(key: &[u8; KEY_LEN]) -> Aes256Gcm {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+}
+
+/// Encrypt `plaintext` under a fresh random nonce, returning `nonce ||
+/// ciphertext`.
+pub fn seal(cipher: &Aes256Gcm, plaintext: &[u8]) -> Vec<u8> {
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    // Only fails if `plaintext` exceeds AES-GCM's length limit (~64 GiB),
+    // which a single word or serialized vault never will.
+    let ciphertext = cipher.encrypt(nonce, plaintext).expect("encryption failed");
+
+    let mut payload = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    payload.extend_from_slice(&nonce_bytes);
+    payload.extend_from_slice(&ciphertext);
+    payload
+}
+
+/// Decrypt a payload produced by `seal`. Returns `None` if it's too short to
+/// contain a nonce or the ciphertext fails to authenticate.
+pub fn open(cipher: &Aes256Gcm, payload: &[u8]) -> Option<Vec<u8>> {
+    if payload.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce_bytes, ciphertext) = payload.split_at(NONCE_LEN);
+    cipher.decrypt(Nonce::from_slice(nonce_bytes), ciphertext).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_then_open_round_trips() {
+        let cipher = cipher_from_key(&[7u8; KEY_LEN]);
+        let payload = seal(&cipher, b"hello world");
+        assert_eq!(open(&cipher, &payload).as_deref(), Some(&b"hello world"[..]));
+    }
+
+    #[test]
+    fn open_rejects_a_tampered_payload() {
+        let cipher = cipher_from_key(&[7u8; KEY_LEN]);
+        let mut payload = seal(&cipher, b"hello world");
+        let last = payload.len() - 1;
+        payload[last] ^= 0xff;
+        assert_eq!(open(&cipher, &payload), None);
+    }
+
+    #[test]
+    fn open_rejects_the_wrong_key() {
+        let sealed_with = cipher_from_key(&[7u8; KEY_LEN]);
+        let opened_with = cipher_from_key(&[8u8; KEY_LEN]);
+        let payload = seal(&sealed_with, b"hello world");
+        assert_eq!(open(&opened_with, &payload), None);
+    }
+}
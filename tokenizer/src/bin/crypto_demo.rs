@@ -0,0 +1,29 @@
+use tokenizer::stores::CryptoStore;
+use tokenizer::{detokenize_entities, tokenize_entities, Detector, KeyedTokenGenerator};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // In production this key comes from a secrets manager; it must stay
+    // fixed for tokens minted under it to remain decryptable across runs.
+    // Each token uses a fresh random nonce, so the same word tokenizes
+    // differently every time.
+    let key = [7u8; 32];
+    let generator = KeyedTokenGenerator::new(b"demo-only-change-me".to_vec());
+    let detectors = [Detector::ssn(), Detector::email(), Detector::phone(), Detector::credit_card()];
+
+    // Tokenize with one CryptoStore instance...
+    let mut tokenizing_store = CryptoStore::new(key);
+    let sensitive_data = "My age is 43. My ssn is 110-10-1010";
+    let tokenized = tokenize_entities(&mut tokenizing_store, &generator, &detectors, sensitive_data).await?;
+    println!("Original: {}", sensitive_data);
+    println!("Tokenized: {}", tokenized);
+
+    // ...and detokenize with a second, unrelated instance built from the
+    // same key: CryptoStore has nothing to persist, so any number of
+    // instances sharing a key are mutually detokenizable.
+    let detokenizing_store = CryptoStore::new(key);
+    let retrieved = detokenize_entities(&detokenizing_store, &tokenized).await?;
+    println!("Retrieved: {}", retrieved);
+
+    Ok(())
+}
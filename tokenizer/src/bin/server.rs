@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::env;
+
+use tokio::sync::Mutex;
+
+use actix_web::dev::ServiceRequest;
+use actix_web::{web, App, Error, HttpResponse, HttpServer, Responder};
+use actix_web_httpauth::extractors::bearer::{BearerAuth, Config};
+use actix_web_httpauth::extractors::AuthenticationError;
+use actix_web_httpauth::middleware::HttpAuthentication;
+use argon2::password_hash::{PasswordHash, PasswordVerifier};
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
+
+use tokenizer::auth::{issue_access_token, issue_refresh_token, validate_token};
+use tokenizer::stores::SledStore;
+use tokenizer::{
+    detokenize_entities, finish_rotation, rotate_key, tokenize_entities, Detector,
+    KeyedTokenGenerator, RotatableStore, TokenStore,
+};
+
+// A caller-supplied regex detector, named so the response/audit trail can
+// reference it the same way as a built-in category.
+#[derive(Deserialize)]
+struct CustomDetector {
+    label: String,
+    pattern: String,
+}
+
+// New struct for the tokenization request
+#[derive(Deserialize)]
+struct TokenizationRequest {
+    input: String,
+    /// Built-in categories to tokenize: any of "ssn", "email", "phone",
+    /// "credit_card". Defaults to all of them when omitted.
+    #[serde(default)]
+    detectors: Option<Vec<String>>,
+    #[serde(default)]
+    custom_detectors: Vec<CustomDetector>,
+}
+
+fn requested_detectors(
+    names: &Option<Vec<String>>,
+    custom: &[CustomDetector],
+) -> Result<Vec<Detector>, String> {
+    let mut detectors = Vec::new();
+    let names = names.clone().unwrap_or_else(|| {
+        vec!["ssn".into(), "email".into(), "phone".into(), "credit_card".into()]
+    });
+    for name in names {
+        detectors.push(match name.as_str() {
+            "ssn" => Detector::ssn(),
+            "email" => Detector::email(),
+            "phone" => Detector::phone(),
+            "credit_card" => Detector::credit_card(),
+            other => return Err(format!("unknown detector: {other}")),
+        });
+    }
+    for custom in custom {
+        if !custom.label.chars().all(|c| c.is_ascii_uppercase() || c == '_') || custom.label.is_empty()
+        {
+            return Err(format!(
+                "invalid label {:?}: must be non-empty and match [A-Z_]+ so detokenize_entities can find it",
+                custom.label
+            ));
+        }
+        let detector = Detector::new(&custom.label, &custom.pattern)
+            .map_err(|e| format!("invalid pattern for {}: {e}", custom.label))?;
+        detectors.push(detector);
+    }
+    Ok(detectors)
+}
+
+// New struct for the tokenization response
+#[derive(Serialize)]
+struct TokenizationResponse {
+    tokenized: String,
+}
+
+#[derive(Deserialize)]
+struct DetokenizationRequest {
+    tokenized: String,
+}
+
+#[derive(Serialize)]
+struct DetokenizationResponse {
+    input: String,
+}
+
+#[derive(Deserialize)]
+struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+struct LoginResponse {
+    access_token: String,
+    refresh_token: String,
+}
+
+#[derive(Deserialize)]
+struct RotateRequest {
+    new_key: String,
+}
+
+#[derive(Serialize)]
+struct RotateResponse {
+    new_key_version: u32,
+    words_rotated: usize,
+}
+
+#[derive(Deserialize)]
+struct FinishRotationRequest {
+    old_key_version: u32,
+}
+
+#[derive(Serialize)]
+struct FinishRotationResponse {
+    tokens_purged: usize,
+}
+
+// Wrap the store/generator in Mutexes for thread-safe access
+struct AppState<T: TokenStore> {
+    store: Mutex<T>,
+    generator: Mutex<KeyedTokenGenerator>,
+    jwt_secret: Vec<u8>,
+    // username -> Argon2 PHC hash. A real deployment would back this with a
+    // user table; a single env-configured account is enough for this demo.
+    users: HashMap<String, String>,
+    // Usernames allowed to hit /rotate.
+    admin_usernames: Vec<String>,
+}
+
+// Handler for the tokenization endpoint
+async fn tokenize_handler<T: TokenStore + 'static>(
+    data: web::Data<AppState<T>>,
+    req: web::Json<TokenizationRequest>,
+) -> impl Responder {
+    let detectors = match requested_detectors(&req.detectors, &req.custom_detectors) {
+        Ok(detectors) => detectors,
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+
+    let mut store = data.store.lock().await;
+    let generator = data.generator.lock().await;
+    match tokenize_entities(&mut *store, &generator, &detectors, &req.input).await {
+        Ok(tokenized) => HttpResponse::Ok().json(TokenizationResponse { tokenized }),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+// Handler for the detokenization endpoint
+async fn detokenize_handler<T: TokenStore + 'static>(
+    data: web::Data<AppState<T>>,
+    req: web::Json<DetokenizationRequest>,
+) -> impl Responder {
+    let store = data.store.lock().await;
+    match detokenize_entities(&*store, &req.tokenized).await {
+        Ok(input) => HttpResponse::Ok().json(DetokenizationResponse { input }),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+/// Check `password` against an Argon2 PHC hash. `Ok(false)` means the
+/// password was wrong; `Err` means `stored_hash` itself wasn't a valid PHC
+/// string.
+fn verify_password(password: &str, stored_hash: &str) -> Result<bool, argon2::password_hash::Error> {
+    let parsed_hash = PasswordHash::new(stored_hash)?;
+    Ok(Argon2::default().verify_password(password.as_bytes(), &parsed_hash).is_ok())
+}
+
+async fn login_handler<T: TokenStore + 'static>(
+    data: web::Data<AppState<T>>,
+    req: web::Json<LoginRequest>,
+) -> impl Responder {
+    let Some(stored_hash) = data.users.get(&req.username) else {
+        return HttpResponse::Unauthorized().finish();
+    };
+    match verify_password(&req.password, stored_hash) {
+        Ok(true) => {}
+        Ok(false) => return HttpResponse::Unauthorized().finish(),
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    }
+
+    let (Ok(access_token), Ok(refresh_token)) = (
+        issue_access_token(&req.username, &data.jwt_secret),
+        issue_refresh_token(&req.username, &data.jwt_secret),
+    ) else {
+        return HttpResponse::InternalServerError().finish();
+    };
+    HttpResponse::Ok().json(LoginResponse { access_token, refresh_token })
+}
+
+// Admin-only: rotate the HMAC key used to derive tokens, re-deriving a
+// token for every word the store already knows about. The old key stays
+// valid for lookups until an operator separately calls `finish_rotation`.
+async fn rotate_handler<T: RotatableStore + 'static>(
+    data: web::Data<AppState<T>>,
+    credentials: BearerAuth,
+    req: web::Json<RotateRequest>,
+) -> impl Responder {
+    let Ok(claims) = validate_token(credentials.token(), &data.jwt_secret) else {
+        return HttpResponse::Unauthorized().finish();
+    };
+    if !data.admin_usernames.iter().any(|u| u == &claims.sub) {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let mut store = data.store.lock().await;
+    let mut generator = data.generator.lock().await;
+    match rotate_key(&mut *store, &mut generator, req.new_key.clone().into_bytes()).await {
+        Ok(report) => HttpResponse::Ok().json(RotateResponse {
+            new_key_version: report.new_key_version,
+            words_rotated: report.words_rotated,
+        }),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+// Admin-only: end the grace window for `old_key_version`, purging every
+// token still tagged with it so a leaked pre-rotation document can no
+// longer be detokenized.
+async fn finish_rotation_handler<T: RotatableStore + 'static>(
+    data: web::Data<AppState<T>>,
+    credentials: BearerAuth,
+    req: web::Json<FinishRotationRequest>,
+) -> impl Responder {
+    let Ok(claims) = validate_token(credentials.token(), &data.jwt_secret) else {
+        return HttpResponse::Unauthorized().finish();
+    };
+    if !data.admin_usernames.iter().any(|u| u == &claims.sub) {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let mut store = data.store.lock().await;
+    let mut generator = data.generator.lock().await;
+    match finish_rotation(&mut *store, &mut generator, req.old_key_version).await {
+        Ok(tokens_purged) => HttpResponse::Ok().json(FinishRotationResponse { tokens_purged }),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+// Validates the `Authorization: Bearer <jwt>` header for any route wrapped
+// in the auth middleware, rejecting with 401 on a bad signature or an
+// expired token.
+async fn jwt_validator<T: TokenStore + 'static>(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    let secret = req.app_data::<web::Data<AppState<T>>>().map(|data| data.jwt_secret.clone());
+    match secret {
+        Some(secret) if validate_token(credentials.token(), &secret).is_ok() => Ok(req),
+        _ => {
+            let config = req.app_data::<Config>().cloned().unwrap_or_default();
+            Err((AuthenticationError::from(config).into(), req))
+        }
+    }
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let store = SledStore::new("token_vault.db").expect("Failed to create vault");
+    let jwt_secret = env::var("JWT_SECRET")
+        .expect("JWT_SECRET must be set")
+        .into_bytes();
+    let generator = KeyedTokenGenerator::new(
+        env::var("TOKEN_HMAC_KEY").expect("TOKEN_HMAC_KEY must be set").into_bytes(),
+    );
+
+    // Demo account; swap for a real user table before going to production.
+    let mut users = HashMap::new();
+    if let (Ok(username), Ok(password_hash)) =
+        (env::var("APP_USERNAME"), env::var("APP_PASSWORD_HASH"))
+    {
+        users.insert(username, password_hash);
+    }
+
+    let admin_usernames = env::var("ADMIN_USERNAMES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::to_string)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let app_state = web::Data::new(AppState {
+        store: Mutex::new(store),
+        generator: Mutex::new(generator),
+        jwt_secret,
+        users,
+        admin_usernames,
+    });
+
+    HttpServer::new(move || {
+        let auth = HttpAuthentication::bearer(jwt_validator::<SledStore>);
+        App::new()
+            .app_data(app_state.clone())
+            .route("/login", web::post().to(login_handler::<SledStore>))
+            .service(
+                web::scope("")
+                    .wrap(auth)
+                    .route("/tokenize", web::post().to(tokenize_handler::<SledStore>))
+                    .route("/detokenize", web::post().to(detokenize_handler::<SledStore>))
+                    .route("/rotate", web::post().to(rotate_handler::<SledStore>))
+                    .route(
+                        "/rotate/finish",
+                        web::post().to(finish_rotation_handler::<SledStore>),
+                    ),
+            )
+    })
+    .bind("127.0.0.1:8080")?
+    .run()
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argon2::password_hash::{PasswordHasher, SaltString};
+
+    fn hash(password: &str) -> String {
+        let salt = SaltString::generate(&mut rand::rngs::OsRng);
+        Argon2::default().hash_password(password.as_bytes(), &salt).unwrap().to_string()
+    }
+
+    #[test]
+    fn verify_password_accepts_the_right_password() {
+        let stored = hash("correct horse battery staple");
+        assert_eq!(verify_password("correct horse battery staple", &stored), Ok(true));
+    }
+
+    #[test]
+    fn verify_password_rejects_the_wrong_password() {
+        let stored = hash("correct horse battery staple");
+        assert_eq!(verify_password("wrong password", &stored), Ok(false));
+    }
+
+    #[test]
+    fn verify_password_errors_on_a_malformed_hash() {
+        assert!(verify_password("anything", "not a phc hash").is_err());
+    }
+
+    #[test]
+    fn requested_detectors_rejects_a_non_uppercase_custom_label() {
+        let custom =
+            vec![CustomDetector { label: "creditCardLike".to_string(), pattern: r"\d+".to_string() }];
+        assert!(requested_detectors(&Some(Vec::new()), &custom).is_err());
+    }
+
+    #[test]
+    fn requested_detectors_accepts_an_uppercase_custom_label() {
+        let custom =
+            vec![CustomDetector { label: "CREDIT_CARD_LIKE".to_string(), pattern: r"\d+".to_string() }];
+        assert!(requested_detectors(&Some(Vec::new()), &custom).is_ok());
+    }
+}
@@ -0,0 +1,41 @@
+use regex::{Matches, Regex};
+
+/// A named pattern whose matches get replaced with a token instead of
+/// passing through verbatim. The label becomes the token's prefix (e.g.
+/// `SSN_<token>`) so detokenization and downstream audit tooling can tell
+/// what kind of entity a given token represents.
+pub struct Detector {
+    pub label: String,
+    pattern: Regex,
+}
+
+impl Detector {
+    /// Build a detector from a label and a regex supplied by the caller
+    /// (e.g. a per-request custom pattern).
+    pub fn new(label: impl Into<String>, pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self { label: label.into(), pattern: Regex::new(pattern)? })
+    }
+
+    pub fn ssn() -> Self {
+        Self::new("SSN", r"\b\d{3}-\d{2}-\d{4}\b").expect("valid regex")
+    }
+
+    pub fn email() -> Self {
+        Self::new("EMAIL", r"\b[\w.+-]+@[\w-]+\.[\w.-]+\b").expect("valid regex")
+    }
+
+    pub fn phone() -> Self {
+        Self::new("PHONE", r"\b\+?\d{1,2}[ -]?\(?\d{3}\)?[ -]?\d{3}[ -]?\d{4}\b")
+            .expect("valid regex")
+    }
+
+    /// Digit runs long enough to plausibly be a card number, with optional
+    /// spaces or dashes between groups.
+    pub fn credit_card() -> Self {
+        Self::new("CREDIT_CARD", r"\b(?:\d[ -]?){13,16}\b").expect("valid regex")
+    }
+
+    pub(crate) fn find_iter<'r, 'h>(&'r self, haystack: &'h str) -> Matches<'r, 'h> {
+        self.pattern.find_iter(haystack)
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::token_store::TokenStore;
+
+fn entity_token_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?P<label>[A-Z_]+)_(?P<token>v\d+\.[A-Za-z0-9_-]+)").expect("valid regex")
+    })
+}
+
+/// Reverse [`crate::tokenize::tokenize_entities`]: replace every
+/// `LABEL_vN.token` span in `input` with the word the store has for
+/// `token`, leaving everything else untouched. A span whose token is
+/// unknown to the store is left as-is rather than erroring, since it may
+/// simply not have come from this vault.
+pub async fn detokenize_entities<T: TokenStore>(store: &T, input: &str) -> Result<String, T::Error> {
+    let pattern = entity_token_pattern();
+    let mut output = String::with_capacity(input.len());
+    let mut cursor = 0;
+
+    // `captures_at` (rather than `captures_iter`) lets us resume the search
+    // from wherever we decide a token actually ended, not just from the end
+    // of the greedy match.
+    while let Some(caps) = pattern.captures_at(input, cursor) {
+        let whole = caps.get(0).expect("whole match always present");
+        output.push_str(&input[cursor..whole.start()]);
+
+        let token_match = caps.name("token").expect("token group always present");
+        // The token body's character class is a superset of a label's, so
+        // when two tokenized spans sit back-to-back with nothing between
+        // them, the greedy match above swallows the next entity's whole
+        // `LABEL_vN.token` text as if it were part of this token. Try the
+        // full greedy match first (the common case, one lookup), then
+        // shrink it a character at a time until the store recognizes a
+        // prefix as a real token, so a trailing swallowed entity is left
+        // for the next iteration instead of being eaten here.
+        let resolved = {
+            let mut found = None;
+            for len in (1..=token_match.len()).rev() {
+                let candidate = &token_match.as_str()[..len];
+                if let Some(word) = store.get_word(candidate).await? {
+                    found = Some((word, token_match.start() + len));
+                    break;
+                }
+            }
+            found
+        };
+
+        match resolved {
+            Some((word, end)) => {
+                output.push_str(&word);
+                cursor = end;
+            }
+            None => {
+                output.push_str(whole.as_str());
+                cursor = whole.end();
+            }
+        }
+    }
+    output.push_str(&input[cursor..]);
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::Detector;
+    use crate::keyed_token::KeyedTokenGenerator;
+    use crate::stores::HashMapStore;
+    use crate::tokenize::tokenize_entities;
+
+    #[tokio::test]
+    async fn tokenize_then_detokenize_round_trips_for_an_uppercase_custom_label() {
+        let mut store = HashMapStore::new();
+        let generator = KeyedTokenGenerator::new(b"key".to_vec());
+        let detectors = [Detector::new("CREDIT_CARD_LIKE", r"\d{6}").unwrap()];
+
+        let input = "account 123456 on file";
+        let tokenized = tokenize_entities(&mut store, &generator, &detectors, input).await.unwrap();
+        assert!(tokenized.contains("CREDIT_CARD_LIKE_v0."));
+
+        let restored = detokenize_entities(&store, &tokenized).await.unwrap();
+        assert_eq!(restored, input);
+    }
+
+    #[tokio::test]
+    async fn round_trips_two_entities_with_no_separating_text() {
+        let mut store = HashMapStore::new();
+        let generator = KeyedTokenGenerator::new(b"key".to_vec());
+        let detectors = [Detector::new("A", "xxxx").unwrap(), Detector::new("B", "yyyy").unwrap()];
+
+        let input = "xxxxyyyy";
+        let tokenized = tokenize_entities(&mut store, &generator, &detectors, input).await.unwrap();
+
+        let restored = detokenize_entities(&store, &tokenized).await.unwrap();
+        assert_eq!(restored, input);
+    }
+}
@@ -0,0 +1,74 @@
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const DEFAULT_TOKEN_LEN: usize = 16;
+
+/// Derives a deterministic token for a word from a fixed secret key, so the
+/// same word always maps to the same token under that key. This is what
+/// lets `set_token` be idempotent and lets two independent instances mint
+/// identical tokens for the same input without coordinating.
+///
+/// Keys are versioned: `rotate` adds a new current key without discarding
+/// old ones, so tokens minted before a rotation keep verifying against the
+/// key they were tagged with until `retire_key` drops it.
+pub struct KeyedTokenGenerator {
+    keys: Vec<(u32, Vec<u8>)>, // ascending by version; last is current
+    token_len: usize,
+}
+
+impl KeyedTokenGenerator {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self { keys: vec![(0, key.into())], token_len: DEFAULT_TOKEN_LEN }
+    }
+
+    /// Truncate tokens to `token_len` base64 characters instead of the
+    /// default. Shorter tokens are more readable but collide more often.
+    pub fn with_token_len(mut self, token_len: usize) -> Self {
+        self.token_len = token_len;
+        self
+    }
+
+    pub fn current_version(&self) -> u32 {
+        self.keys.last().expect("a generator always has at least one key").0
+    }
+
+    /// Derive `word`'s token under the current key, tagged with its
+    /// version.
+    pub fn token_for(&self, word: &str) -> String {
+        self.token_for_version(self.current_version(), word)
+            .expect("current version is always present")
+    }
+
+    /// Derive `word`'s token under a specific key version, if that version
+    /// is still in the ring. Used to re-derive tokens during rotation.
+    pub fn token_for_version(&self, version: u32, word: &str) -> Option<String> {
+        let (_, key) = self.keys.iter().find(|(v, _)| *v == version)?;
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(word.as_bytes());
+        let digest = mac.finalize().into_bytes();
+        let encoded = general_purpose::URL_SAFE_NO_PAD.encode(digest);
+        Some(format!("v{version}.{}", &encoded[..self.token_len.min(encoded.len())]))
+    }
+
+    /// Parse the `vN.` version tag off a token minted by this generator.
+    pub fn version_of(token: &str) -> Option<u32> {
+        token.strip_prefix('v')?.split_once('.')?.0.parse().ok()
+    }
+
+    /// Make `new_key` the current key, returning its version. Older keys
+    /// stay in the ring so tokens minted under them still resolve, until
+    /// [`Self::retire_key`] drops one.
+    pub fn rotate(&mut self, new_key: impl Into<Vec<u8>>) -> u32 {
+        let version = self.current_version() + 1;
+        self.keys.push((version, new_key.into()));
+        version
+    }
+
+    /// Stop honoring `version`, e.g. once its grace window has elapsed.
+    pub fn retire_key(&mut self, version: u32) {
+        self.keys.retain(|(v, _)| *v != version);
+    }
+}
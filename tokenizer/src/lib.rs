@@ -0,0 +1,17 @@
+#[cfg(feature = "crypto")]
+pub mod aead;
+pub mod auth;
+pub mod detectors;
+pub mod detokenize;
+pub mod keyed_token;
+pub mod rotation;
+pub mod stores;
+pub mod token_store;
+pub mod tokenize;
+
+pub use detectors::Detector;
+pub use detokenize::detokenize_entities;
+pub use keyed_token::KeyedTokenGenerator;
+pub use rotation::{finish_rotation, rotate_key, RotationError, RotationReport};
+pub use token_store::{RotatableStore, TokenStore};
+pub use tokenize::{tokenize, tokenize_entities, TokenizeError};
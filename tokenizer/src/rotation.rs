@@ -0,0 +1,109 @@
+// Bulk re-tokenization for key rotation: re-derive every known word's token
+// under a new key without invalidating documents tokenized under the old
+// one while its grace window is still open.
+use std::fmt;
+
+use crate::keyed_token::KeyedTokenGenerator;
+use crate::token_store::RotatableStore;
+
+#[derive(Debug)]
+pub struct RotationReport {
+    pub new_key_version: u32,
+    pub words_rotated: usize,
+}
+
+#[derive(Debug)]
+pub enum RotationError<E> {
+    Store(E),
+}
+
+impl<E: fmt::Display> fmt::Display for RotationError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Store(e) => write!(f, "store error during rotation: {e}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for RotationError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Store(e) => Some(e),
+        }
+    }
+}
+
+/// Rotate `generator` onto `new_key` and write a freshly-derived token for
+/// every word the store already knows about. The previous key (and the
+/// tokens it minted) stay valid until [`finish_rotation`] retires it, so
+/// already-tokenized documents keep detokenizing during the grace window.
+pub async fn rotate_key<S: RotatableStore>(
+    store: &mut S,
+    generator: &mut KeyedTokenGenerator,
+    new_key: impl Into<Vec<u8>>,
+) -> Result<RotationReport, RotationError<S::Error>> {
+    let entries = store.entries().await.map_err(RotationError::Store)?;
+    let new_version = generator.rotate(new_key);
+
+    for (word, _old_token) in &entries {
+        let new_token = generator
+            .token_for_version(new_version, word)
+            .expect("the version just added is always present");
+        store.set_token(word, &new_token).await.map_err(RotationError::Store)?;
+    }
+
+    Ok(RotationReport { new_key_version: new_version, words_rotated: entries.len() })
+}
+
+/// End the grace window for `old_version`: the generator stops accepting
+/// it, and every token still tagged with it is purged from the store so a
+/// leaked pre-rotation document can no longer be detokenized.
+pub async fn finish_rotation<S: RotatableStore>(
+    store: &mut S,
+    generator: &mut KeyedTokenGenerator,
+    old_version: u32,
+) -> Result<usize, RotationError<S::Error>> {
+    let all_tokens = store.all_tokens().await.map_err(RotationError::Store)?;
+    let mut purged = 0;
+    for (token, _word) in all_tokens {
+        if KeyedTokenGenerator::version_of(&token) == Some(old_version) {
+            store.remove_token(&token).await.map_err(RotationError::Store)?;
+            purged += 1;
+        }
+    }
+    generator.retire_key(old_version);
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stores::HashMapStore;
+    use crate::token_store::TokenStore;
+
+    #[tokio::test]
+    async fn finish_rotation_purges_only_the_old_version_and_keeps_new_tokens_live() {
+        let mut store = HashMapStore::new();
+        let mut generator = KeyedTokenGenerator::new(b"old-key".to_vec());
+
+        let old_token = generator.token_for("alice");
+        store.set_token("alice", &old_token).await.unwrap();
+
+        let report = rotate_key(&mut store, &mut generator, b"new-key".to_vec()).await.unwrap();
+        assert_eq!(report.words_rotated, 1);
+
+        let new_token = store.get_token("alice").await.unwrap().unwrap();
+        assert_ne!(new_token, old_token);
+        // Old token still resolves during the grace window.
+        assert_eq!(store.get_word(&old_token).await.unwrap().as_deref(), Some("alice"));
+
+        let purged = finish_rotation(&mut store, &mut generator, 0).await.unwrap();
+        assert_eq!(purged, 1);
+
+        // Old token is gone, but the current one (and the word's live
+        // mapping) survived the purge.
+        assert_eq!(store.get_word(&old_token).await.unwrap(), None);
+        assert_eq!(store.get_word(&new_token).await.unwrap().as_deref(), Some("alice"));
+        assert_eq!(store.get_token("alice").await.unwrap().as_deref(), Some(new_token.as_str()));
+    }
+}
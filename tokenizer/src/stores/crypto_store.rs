@@ -0,0 +1,180 @@
+use std::env;
+use std::fmt;
+use std::fs;
+
+use aes_gcm::Aes256Gcm;
+use base64::{engine::general_purpose, Engine as _};
+
+use crate::aead::{self, KEY_LEN};
+use crate::token_store::TokenStore;
+
+#[derive(Debug)]
+pub enum CryptoStoreError {
+    /// The configured key (from env or file) wasn't exactly 32 bytes.
+    InvalidKeyLength(usize),
+    /// The environment variable passed to `from_env` was not set.
+    KeyNotConfigured,
+    Io(std::io::Error),
+    /// The token had no `vN.` version tag, wasn't valid base64, or
+    /// decrypted to invalid UTF-8. Also returned when a configured key
+    /// isn't valid base64.
+    MalformedToken,
+    /// No key in the ring matches the token's version tag (it's already
+    /// been retired), or AES-GCM rejected the ciphertext under that key
+    /// (wrong key or tampered token).
+    DecryptionFailed,
+}
+
+impl fmt::Display for CryptoStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidKeyLength(n) => write!(f, "key must be {KEY_LEN} bytes, got {n}"),
+            Self::KeyNotConfigured => write!(f, "no encryption key configured"),
+            Self::Io(e) => write!(f, "failed to read key file: {e}"),
+            Self::MalformedToken => write!(f, "token is not valid base64/utf-8"),
+            Self::DecryptionFailed => write!(f, "failed to decrypt token"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoStoreError {}
+
+impl From<std::io::Error> for CryptoStoreError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// A stateless `TokenStore` backed by AES-256-GCM instead of a persisted
+/// map. A token is `vN.<base64 of nonce || ciphertext>`, so `get_word` can
+/// decrypt it directly without ever having stored the mapping. This means
+/// the vault can't grow unbounded and any number of instances sharing the
+/// same key produce tokens that are all mutually detokenizable.
+///
+/// Keys are versioned like [`crate::keyed_token::KeyedTokenGenerator`]:
+/// `rotate_key` adds a new current key without discarding old ones, so
+/// tokens minted before a rotation keep decrypting until `retire_key` drops
+/// the key they were tagged with.
+pub struct CryptoStore {
+    ciphers: Vec<(u32, Aes256Gcm)>, // ascending by version; last is current
+}
+
+impl CryptoStore {
+    pub fn new(key: [u8; KEY_LEN]) -> Self {
+        Self { ciphers: vec![(0, aead::cipher_from_key(&key))] }
+    }
+
+    /// Load the key from the named environment variable, expecting
+    /// url-safe-base64-encoded 32 raw bytes.
+    pub fn from_env(var: &str) -> Result<Self, CryptoStoreError> {
+        let encoded = env::var(var).map_err(|_| CryptoStoreError::KeyNotConfigured)?;
+        Self::from_encoded_key(&encoded)
+    }
+
+    /// Load the key from a file containing url-safe-base64-encoded 32 raw
+    /// bytes.
+    pub fn from_key_file(path: &str) -> Result<Self, CryptoStoreError> {
+        let encoded = fs::read_to_string(path)?;
+        Self::from_encoded_key(encoded.trim())
+    }
+
+    fn from_encoded_key(encoded: &str) -> Result<Self, CryptoStoreError> {
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| CryptoStoreError::MalformedToken)?;
+        let key: [u8; KEY_LEN] =
+            bytes.try_into().map_err(|b: Vec<u8>| CryptoStoreError::InvalidKeyLength(b.len()))?;
+        Ok(Self::new(key))
+    }
+
+    fn current_version(&self) -> u32 {
+        self.ciphers.last().expect("a store always has at least one key").0
+    }
+
+    /// Encrypt `word` under the current key and a fresh random nonce,
+    /// returning a url-safe base64 token tagged with the key's version.
+    pub fn encrypt_word(&self, word: &str) -> String {
+        let (version, cipher) = self.ciphers.last().expect("a store always has at least one key");
+        let payload = general_purpose::URL_SAFE_NO_PAD.encode(aead::seal(cipher, word.as_bytes()));
+        format!("v{version}.{payload}")
+    }
+
+    /// Decrypt a token minted by `encrypt_word` back to its original word,
+    /// using whichever key in the ring matches its version tag.
+    pub fn decrypt_token(&self, token: &str) -> Result<String, CryptoStoreError> {
+        let (version_tag, payload) = token.split_once('.').ok_or(CryptoStoreError::MalformedToken)?;
+        let version: u32 =
+            version_tag.strip_prefix('v').ok_or(CryptoStoreError::MalformedToken)?.parse().map_err(|_| CryptoStoreError::MalformedToken)?;
+        let cipher = self
+            .ciphers
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(_, cipher)| cipher)
+            .ok_or(CryptoStoreError::DecryptionFailed)?;
+
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| CryptoStoreError::MalformedToken)?;
+        let plaintext = aead::open(cipher, &bytes).ok_or(CryptoStoreError::DecryptionFailed)?;
+        String::from_utf8(plaintext).map_err(|_| CryptoStoreError::MalformedToken)
+    }
+
+    /// Make `new_key` the current key, returning its version. Older keys
+    /// stay in the ring so tokens minted under them still decrypt, until
+    /// [`Self::retire_key`] drops one.
+    pub fn rotate_key(&mut self, new_key: [u8; KEY_LEN]) -> u32 {
+        let version = self.current_version() + 1;
+        self.ciphers.push((version, aead::cipher_from_key(&new_key)));
+        version
+    }
+
+    /// Stop honoring `version`, e.g. once its grace window has elapsed.
+    pub fn retire_key(&mut self, version: u32) {
+        self.ciphers.retain(|(v, _)| *v != version);
+    }
+}
+
+impl TokenStore for CryptoStore {
+    type Error = CryptoStoreError;
+
+    // Nothing to persist: the token itself carries the word.
+    async fn set_token(&mut self, _word: &str, _token: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn get_token(&self, word: &str) -> Result<Option<String>, Self::Error> {
+        Ok(Some(self.encrypt_word(word)))
+    }
+
+    // A token this store can't decrypt isn't one it minted, so report it as
+    // unknown rather than as an error.
+    async fn get_word(&self, token: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.decrypt_token(token).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_then_decrypt_round_trips() {
+        let store = CryptoStore::new([1u8; KEY_LEN]);
+        let token = store.encrypt_word("hello");
+        assert!(token.starts_with("v0."));
+        assert_eq!(store.decrypt_token(&token).unwrap(), "hello");
+    }
+
+    #[test]
+    fn decrypt_keeps_working_for_the_old_key_until_retired() {
+        let mut store = CryptoStore::new([1u8; KEY_LEN]);
+        let old_token = store.encrypt_word("hello");
+
+        let new_version = store.rotate_key([2u8; KEY_LEN]);
+        assert_eq!(new_version, 1);
+        assert_eq!(store.decrypt_token(&old_token).unwrap(), "hello");
+
+        store.retire_key(0);
+        assert!(matches!(store.decrypt_token(&old_token), Err(CryptoStoreError::DecryptionFailed)));
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use crate::token_store::{RotatableStore, TokenStore};
+
+/// Plain in-memory store. Nothing is persisted, so it's mainly useful for
+/// tests and for examples where standing up sled or SQL Server is overkill.
+#[derive(Default)]
+pub struct HashMapStore {
+    word_to_token: HashMap<String, String>,
+    token_to_word: HashMap<String, String>,
+}
+
+impl HashMapStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenStore for HashMapStore {
+    type Error = Infallible;
+
+    async fn set_token(&mut self, word: &str, token: &str) -> Result<(), Self::Error> {
+        self.word_to_token.insert(word.to_string(), token.to_string());
+        self.token_to_word.insert(token.to_string(), word.to_string());
+        Ok(())
+    }
+
+    async fn get_token(&self, word: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.word_to_token.get(word).cloned())
+    }
+
+    async fn get_word(&self, token: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.token_to_word.get(token).cloned())
+    }
+}
+
+impl RotatableStore for HashMapStore {
+    async fn entries(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        Ok(self.word_to_token.iter().map(|(w, t)| (w.clone(), t.clone())).collect())
+    }
+
+    async fn all_tokens(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        Ok(self.token_to_word.iter().map(|(t, w)| (t.clone(), w.clone())).collect())
+    }
+
+    async fn remove_token(&mut self, token: &str) -> Result<(), Self::Error> {
+        self.token_to_word.remove(token);
+        Ok(())
+    }
+}
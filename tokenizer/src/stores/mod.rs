@@ -0,0 +1,26 @@
+// Concrete `TokenStore` backends. Each one that needs an extra dependency
+// is feature-gated so a deployment only pulls in the dependency (sled,
+// tiberius, ...) it actually needs; `HashMapStore` needs none and is always
+// available.
+mod hashmap_store;
+pub use hashmap_store::HashMapStore;
+
+#[cfg(feature = "crypto")]
+mod crypto_store;
+#[cfg(feature = "crypto")]
+pub use crypto_store::{CryptoStore, CryptoStoreError};
+
+#[cfg(feature = "sled")]
+mod sled_store;
+#[cfg(feature = "sled")]
+pub use sled_store::SledStore;
+
+#[cfg(feature = "sqlserver")]
+mod sqlserver_store;
+#[cfg(feature = "sqlserver")]
+pub use sqlserver_store::{SqlServerStore, SqlServerStoreError};
+
+#[cfg(feature = "s3")]
+mod s3_store;
+#[cfg(feature = "s3")]
+pub use s3_store::{S3Config, S3Store, S3StoreError};
@@ -0,0 +1,223 @@
+use std::fmt;
+
+use aes_gcm::Aes256Gcm;
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use base64::{engine::general_purpose, Engine as _};
+
+use crate::aead::{self, KEY_LEN};
+use crate::token_store::{RotatableStore, TokenStore};
+
+/// Connection details for AWS S3 or an S3-compatible server such as Garage.
+/// `endpoint` is only needed for the latter; leave it `None` for AWS.
+pub struct S3Config {
+    pub endpoint: Option<String>,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+#[derive(Debug)]
+pub enum S3StoreError {
+    Request(Box<dyn std::error::Error + Send + Sync>),
+    /// The object existed but wasn't `nonce || ciphertext` decryptable under
+    /// the configured key.
+    DecryptionFailed,
+    MalformedObject,
+}
+
+impl fmt::Display for S3StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request(e) => write!(f, "S3 request failed: {e}"),
+            Self::DecryptionFailed => write!(f, "failed to decrypt stored object"),
+            Self::MalformedObject => write!(f, "stored object was not valid utf-8"),
+        }
+    }
+}
+
+impl std::error::Error for S3StoreError {}
+
+/// Stores each word/token pair as a pair of individually-encrypted S3
+/// objects rather than one serialized blob, so the vault scales past what
+/// fits comfortably in memory and a single slow `PutObject` can't block
+/// every other lookup.
+pub struct S3Store {
+    client: Client,
+    bucket: String,
+    cipher: Aes256Gcm,
+}
+
+impl S3Store {
+    pub async fn new(config: S3Config, encryption_key: [u8; KEY_LEN]) -> Self {
+        let mut builder = aws_sdk_s3::config::Builder::new()
+            .region(Region::new(config.region))
+            .credentials_provider(Credentials::new(
+                config.access_key_id,
+                config.secret_access_key,
+                None,
+                None,
+                "rustdatatokenizer",
+            ))
+            .force_path_style(true);
+        if let Some(endpoint) = config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+
+        Self {
+            client: Client::from_conf(builder.build()),
+            bucket: config.bucket,
+            cipher: aead::cipher_from_key(&encryption_key),
+        }
+    }
+
+    fn word_key(word: &str) -> String {
+        format!("word/{}", general_purpose::URL_SAFE_NO_PAD.encode(word))
+    }
+
+    fn token_key(token: &str) -> String {
+        format!("token/{token}")
+    }
+
+    async fn put_encrypted(&self, key: &str, plaintext: &str) -> Result<(), S3StoreError> {
+        let body = aead::seal(&self.cipher, plaintext.as_bytes());
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| S3StoreError::Request(Box::new(e)))?;
+        Ok(())
+    }
+
+    async fn get_decrypted(&self, key: &str) -> Result<Option<String>, S3StoreError> {
+        let result = self.client.get_object().bucket(&self.bucket).key(key).send().await;
+        let object = match result {
+            Ok(object) => object,
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => return Ok(None),
+            Err(e) => return Err(S3StoreError::Request(Box::new(e))),
+        };
+
+        let bytes = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| S3StoreError::Request(Box::new(e)))?
+            .into_bytes();
+        let plaintext = aead::open(&self.cipher, &bytes).ok_or(S3StoreError::DecryptionFailed)?;
+        String::from_utf8(plaintext).map(Some).map_err(|_| S3StoreError::MalformedObject)
+    }
+}
+
+impl TokenStore for S3Store {
+    type Error = S3StoreError;
+
+    async fn set_token(&mut self, word: &str, token: &str) -> Result<(), Self::Error> {
+        self.put_encrypted(&Self::word_key(word), token).await?;
+        self.put_encrypted(&Self::token_key(token), word).await
+    }
+
+    async fn get_token(&self, word: &str) -> Result<Option<String>, Self::Error> {
+        self.get_decrypted(&Self::word_key(word)).await
+    }
+
+    async fn get_word(&self, token: &str) -> Result<Option<String>, Self::Error> {
+        self.get_decrypted(&Self::token_key(token)).await
+    }
+}
+
+impl RotatableStore for S3Store {
+    async fn entries(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        let mut entries = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut request = self.client.list_objects_v2().bucket(&self.bucket).prefix("word/");
+            if let Some(token) = continuation_token {
+                request = request.continuation_token(token);
+            }
+            let response = request.send().await.map_err(|e| S3StoreError::Request(Box::new(e)))?;
+
+            for object in response.contents() {
+                let Some(key) = object.key() else { continue };
+                let Some(encoded) = key.strip_prefix("word/") else { continue };
+                let word_bytes = general_purpose::URL_SAFE_NO_PAD
+                    .decode(encoded)
+                    .map_err(|_| S3StoreError::MalformedObject)?;
+                let word = String::from_utf8(word_bytes).map_err(|_| S3StoreError::MalformedObject)?;
+                if let Some(token) = self.get_decrypted(key).await? {
+                    entries.push((word, token));
+                }
+            }
+
+            continuation_token = response.next_continuation_token().map(str::to_string);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn all_tokens(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        let mut tokens = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut request = self.client.list_objects_v2().bucket(&self.bucket).prefix("token/");
+            if let Some(token) = continuation_token {
+                request = request.continuation_token(token);
+            }
+            let response = request.send().await.map_err(|e| S3StoreError::Request(Box::new(e)))?;
+
+            for object in response.contents() {
+                let Some(key) = object.key() else { continue };
+                let Some(token) = key.strip_prefix("token/") else { continue };
+                if let Some(word) = self.get_decrypted(key).await? {
+                    tokens.push((token.to_string(), word));
+                }
+            }
+
+            continuation_token = response.next_continuation_token().map(str::to_string);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(tokens)
+    }
+
+    async fn remove_token(&mut self, token: &str) -> Result<(), Self::Error> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(Self::token_key(token))
+            .send()
+            .await
+            .map_err(|e| S3StoreError::Request(Box::new(e)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_and_token_keys_land_in_disjoint_prefixes() {
+        // Same underlying string, one keyed as a word and one as a token,
+        // must never collide so a word's object can't be mistaken for a
+        // token's (or vice versa).
+        assert_ne!(S3Store::word_key("same"), S3Store::token_key("same"));
+        assert!(S3Store::word_key("same").starts_with("word/"));
+        assert!(S3Store::token_key("same").starts_with("token/"));
+    }
+
+    #[test]
+    fn word_key_is_stable_and_url_safe() {
+        let key = S3Store::word_key("hello world/needs encoding");
+        assert_eq!(key, S3Store::word_key("hello world/needs encoding"));
+        let encoded = key.strip_prefix("word/").unwrap();
+        assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sled::Db;
+
+use crate::token_store::{RotatableStore, TokenStore};
+
+#[derive(Serialize, Deserialize, Default)]
+struct TokenVault {
+    word_to_token: HashMap<String, String>,
+    token_to_word: HashMap<String, String>,
+}
+
+/// Persists the token vault to a local sled database.
+pub struct SledStore {
+    db: Db,
+    vault: TokenVault,
+}
+
+impl SledStore {
+    pub fn new(path: &str) -> Result<Self, sled::Error> {
+        let db = sled::open(path)?;
+        let vault = match db.get("vault")? {
+            Some(data) => bincode::deserialize(&data).unwrap_or_default(),
+            None => TokenVault::default(),
+        };
+        Ok(Self { db, vault })
+    }
+
+    fn save(&self) -> Result<(), sled::Error> {
+        let encoded = bincode::serialize(&self.vault).unwrap();
+        self.db.insert("vault", encoded)?;
+        self.db.flush().map(|_| ())
+    }
+}
+
+impl TokenStore for SledStore {
+    type Error = sled::Error;
+
+    async fn set_token(&mut self, word: &str, token: &str) -> Result<(), Self::Error> {
+        self.vault.word_to_token.insert(word.to_string(), token.to_string());
+        self.vault.token_to_word.insert(token.to_string(), word.to_string());
+        self.save()
+    }
+
+    async fn get_token(&self, word: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.vault.word_to_token.get(word).cloned())
+    }
+
+    async fn get_word(&self, token: &str) -> Result<Option<String>, Self::Error> {
+        Ok(self.vault.token_to_word.get(token).cloned())
+    }
+}
+
+impl RotatableStore for SledStore {
+    async fn entries(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        Ok(self.vault.word_to_token.iter().map(|(w, t)| (w.clone(), t.clone())).collect())
+    }
+
+    async fn all_tokens(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        Ok(self.vault.token_to_word.iter().map(|(t, w)| (t.clone(), w.clone())).collect())
+    }
+
+    async fn remove_token(&mut self, token: &str) -> Result<(), Self::Error> {
+        self.vault.token_to_word.remove(token);
+        self.save()
+    }
+}
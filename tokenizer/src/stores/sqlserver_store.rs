@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex as StdMutex;
+
+use tiberius::{Client, Config};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+use crate::token_store::TokenStore;
+
+#[derive(Debug)]
+pub enum SqlServerStoreError {
+    Tiberius(tiberius::error::Error),
+    /// A row was returned but the expected column was missing or NULL.
+    MissingColumn(&'static str),
+}
+
+impl fmt::Display for SqlServerStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tiberius(e) => write!(f, "SQL Server error: {e}"),
+            Self::MissingColumn(column) => write!(f, "row was missing expected column `{column}`"),
+        }
+    }
+}
+
+impl std::error::Error for SqlServerStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Tiberius(e) => Some(e),
+            Self::MissingColumn(_) => None,
+        }
+    }
+}
+
+impl From<tiberius::error::Error> for SqlServerStoreError {
+    fn from(e: tiberius::error::Error) -> Self {
+        Self::Tiberius(e)
+    }
+}
+
+impl From<std::io::Error> for SqlServerStoreError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Tiberius(e.into())
+    }
+}
+
+/// Persists the token vault to a SQL Server `TokenVault` table, with a local
+/// word -> token cache so repeated `get_token` calls for the same word don't
+/// round-trip to the database; `get_word` always queries. The tiberius client needs `&mut self` for every call, so it's
+/// behind a `tokio::sync::Mutex` to satisfy `TokenStore::get_token`/
+/// `get_word`'s `&self` signature.
+///
+/// Does not implement [`crate::token_store::RotatableStore`]: `set_token`
+/// overwrites a word's row via `MERGE`, so the table only ever holds each
+/// word's current token. A rotation's grace window depends on the old
+/// token still resolving until its version is retired, which this schema
+/// can't provide. `HashMapStore`, `SledStore`, and `S3Store` keep every
+/// token they've ever minted and support rotation; route through one of
+/// those if you need it.
+pub struct SqlServerStore {
+    client: Mutex<Client<Compat<TcpStream>>>,
+    cache: StdMutex<HashMap<String, String>>,
+}
+
+impl SqlServerStore {
+    pub async fn new(config: Config) -> Result<Self, SqlServerStoreError> {
+        let tcp = TcpStream::connect(config.get_addr()).await?;
+        tcp.set_nodelay(true)?;
+
+        let mut client = Client::connect(config, tcp.compat_write()).await?;
+
+        client
+            .execute(
+                "IF NOT EXISTS (SELECT * FROM sysobjects WHERE name='TokenVault' AND xtype='U')
+                 CREATE TABLE TokenVault (word VARCHAR(255) PRIMARY KEY, token VARCHAR(255) UNIQUE)",
+                &[],
+            )
+            .await?;
+
+        Ok(Self { client: Mutex::new(client), cache: StdMutex::new(HashMap::new()) })
+    }
+}
+
+impl TokenStore for SqlServerStore {
+    type Error = SqlServerStoreError;
+
+    async fn set_token(&mut self, word: &str, token: &str) -> Result<(), Self::Error> {
+        self.client
+            .lock()
+            .await
+            .execute(
+                "MERGE TokenVault AS target
+                 USING (SELECT @P1 AS word, @P2 AS token) AS source
+                 ON target.word = source.word
+                 WHEN MATCHED THEN UPDATE SET token = source.token
+                 WHEN NOT MATCHED THEN INSERT (word, token) VALUES (source.word, source.token);",
+                &[&word, &token],
+            )
+            .await?;
+        self.cache.lock().unwrap().insert(word.to_string(), token.to_string());
+        Ok(())
+    }
+
+    async fn get_token(&self, word: &str) -> Result<Option<String>, Self::Error> {
+        if let Some(token) = self.cache.lock().unwrap().get(word).cloned() {
+            return Ok(Some(token));
+        }
+
+        let row = self
+            .client
+            .lock()
+            .await
+            .query("SELECT token FROM TokenVault WHERE word = @P1", &[&word])
+            .await?
+            .into_row()
+            .await?;
+
+        match row {
+            Some(row) => {
+                let token = row.get::<&str, _>("token").ok_or(SqlServerStoreError::MissingColumn("token"))?;
+                Ok(Some(token.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_word(&self, token: &str) -> Result<Option<String>, Self::Error> {
+        let row = self
+            .client
+            .lock()
+            .await
+            .query("SELECT word FROM TokenVault WHERE token = @P1", &[&token])
+            .await?
+            .into_row()
+            .await?;
+
+        match row {
+            Some(row) => {
+                let word = row.get::<&str, _>("word").ok_or(SqlServerStoreError::MissingColumn("word"))?;
+                Ok(Some(word.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Storage backend abstraction for the tokenizer.
+//
+// Everything above this trait (the tokenizer itself, the CLI, the actix
+// handlers) only talks to `TokenStore`, so swapping sled for SQL Server or an
+// in-memory map for tests is a matter of picking a different implementation,
+// not rewriting `tokenize`.
+use std::error::Error;
+
+/// A place to durably map words to tokens and back.
+///
+/// Implementations are free to keep their own in-memory cache on top of
+/// whatever they persist to, as long as `set_token` is visible to subsequent
+/// `get_token`/`get_word` calls on the same store.
+#[allow(async_fn_in_trait)]
+pub trait TokenStore {
+    type Error: Error + Send + Sync + 'static;
+
+    /// Record that `word` maps to `token` (and `token` maps back to `word`).
+    async fn set_token(&mut self, word: &str, token: &str) -> Result<(), Self::Error>;
+
+    /// Look up the token previously assigned to `word`, if any.
+    async fn get_token(&self, word: &str) -> Result<Option<String>, Self::Error>;
+
+    /// Look up the word a token was generated from, if any.
+    async fn get_word(&self, token: &str) -> Result<Option<String>, Self::Error>;
+}
+
+/// A `TokenStore` that can enumerate everything it holds and remove single
+/// entries. Bulk maintenance jobs like key rotation need this; ordinary
+/// tokenize/detokenize traffic doesn't, which is why it's a separate trait.
+#[allow(async_fn_in_trait)]
+pub trait RotatableStore: TokenStore {
+    /// Every `(word, token)` pair currently live, i.e. what `get_token`
+    /// would return for each word right now.
+    async fn entries(&self) -> Result<Vec<(String, String)>, Self::Error>;
+
+    /// Every `(token, word)` pair this store has ever minted, including
+    /// tokens from a retired key version that are only being kept around
+    /// for their grace window. Distinct from `entries` because a word's
+    /// old and new tokens both live here at once during a rotation.
+    async fn all_tokens(&self) -> Result<Vec<(String, String)>, Self::Error>;
+
+    /// Purge a single token so it can no longer be resolved back to a
+    /// word. Only touches the token -> word mapping; a word's current
+    /// token (which may by now be a different, newer-version one) is
+    /// left alone.
+    async fn remove_token(&mut self, token: &str) -> Result<(), Self::Error>;
+}
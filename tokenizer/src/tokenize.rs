@@ -0,0 +1,165 @@
+use std::fmt;
+
+use crate::detectors::Detector;
+use crate::keyed_token::KeyedTokenGenerator;
+use crate::token_store::TokenStore;
+
+#[derive(Debug)]
+pub enum TokenizeError<E> {
+    Store(E),
+    /// Two different words hashed to the same truncated token under the
+    /// generator's key/length. Increase the token length to make this less
+    /// likely.
+    TokenCollision { word: String, token: String },
+}
+
+impl<E: fmt::Display> fmt::Display for TokenizeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Store(e) => write!(f, "store error: {e}"),
+            Self::TokenCollision { word, token } => {
+                write!(f, "token {token} already maps to a different word than {word:?}")
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for TokenizeError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Store(e) => Some(e),
+            Self::TokenCollision { .. } => None,
+        }
+    }
+}
+
+/// Look up `word`'s token, minting one via `generator` if the store hasn't
+/// seen it before.
+async fn get_or_mint_token<T: TokenStore>(
+    store: &mut T,
+    generator: &KeyedTokenGenerator,
+    word: &str,
+) -> Result<String, TokenizeError<T::Error>> {
+    if let Some(token) = store.get_token(word).await.map_err(TokenizeError::Store)? {
+        return Ok(token);
+    }
+
+    let new_token = generator.token_for(word);
+    if let Some(existing) = store.get_word(&new_token).await.map_err(TokenizeError::Store)? {
+        if existing != word {
+            return Err(TokenizeError::TokenCollision { word: word.to_string(), token: new_token });
+        }
+    }
+    store.set_token(word, &new_token).await.map_err(TokenizeError::Store)?;
+    Ok(new_token)
+}
+
+/// Tokenize `input` word-by-word against `store`, deriving a token for any
+/// word the store hasn't seen before from `generator` instead of retrying a
+/// random draw until one happens not to collide.
+pub async fn tokenize<T: TokenStore>(
+    store: &mut T,
+    generator: &KeyedTokenGenerator,
+    input: &str,
+) -> Result<String, TokenizeError<T::Error>> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut tokenized = Vec::with_capacity(words.len());
+
+    for word in words {
+        tokenized.push(get_or_mint_token(store, generator, word).await?);
+    }
+
+    Ok(tokenized.join(" "))
+}
+
+/// Tokenize only the spans of `input` matched by `detectors`, passing
+/// everything else through verbatim. Each token is prefixed with its
+/// detector's label (e.g. `SSN_v0.xyz`) so detokenization and downstream
+/// audit can tell what category it came from.
+pub async fn tokenize_entities<T: TokenStore>(
+    store: &mut T,
+    generator: &KeyedTokenGenerator,
+    detectors: &[Detector],
+    input: &str,
+) -> Result<String, TokenizeError<T::Error>> {
+    let mut spans: Vec<(usize, usize, &Detector)> = detectors
+        .iter()
+        .flat_map(|detector| detector.find_iter(input).map(move |m| (m.start(), m.end(), detector)))
+        .collect();
+    // Earliest match first; on a tie prefer the longer one so e.g. a phone
+    // detector's match isn't shadowed by a shorter digit-run match starting
+    // at the same position.
+    spans.sort_by_key(|&(start, end, _)| (start, std::cmp::Reverse(end)));
+
+    let mut output = String::with_capacity(input.len());
+    let mut cursor = 0;
+    for (start, end, detector) in spans {
+        if start < cursor {
+            continue; // overlaps a span already tokenized
+        }
+        output.push_str(&input[cursor..start]);
+        let word = &input[start..end];
+        let token = get_or_mint_token(store, generator, word).await?;
+        output.push_str(&detector.label);
+        output.push('_');
+        output.push_str(&token);
+        cursor = end;
+    }
+    output.push_str(&input[cursor..]);
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stores::HashMapStore;
+
+    #[tokio::test]
+    async fn tokenize_is_idempotent_for_the_same_word() {
+        let mut store = HashMapStore::new();
+        let generator = KeyedTokenGenerator::new(b"key".to_vec());
+
+        let first = tokenize(&mut store, &generator, "hello hello").await.unwrap();
+        let (a, b) = first.split_once(' ').unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[tokio::test]
+    async fn tokenize_detects_a_real_collision() {
+        let mut store = HashMapStore::new();
+        // Truncated to one base64 character gives only 64 possible tokens;
+        // under this key "delta" and "foxtrot" both truncate to `v0.0`.
+        let generator = KeyedTokenGenerator::new(b"key".to_vec()).with_token_len(1);
+
+        let mut saw_collision = false;
+        for word in ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"] {
+            match tokenize(&mut store, &generator, word).await {
+                Ok(_) => {}
+                Err(TokenizeError::TokenCollision { .. }) => {
+                    saw_collision = true;
+                    break;
+                }
+                Err(e) => panic!("unexpected error: {e}"),
+            }
+        }
+        assert!(saw_collision, "expected a truncated-token collision");
+    }
+
+    #[tokio::test]
+    async fn tokenize_entities_resolves_overlap_by_longest_match_first() {
+        let mut store = HashMapStore::new();
+        let generator = KeyedTokenGenerator::new(b"key".to_vec());
+        // Two detectors that both match at the same start position, one
+        // matching a longer span than the other.
+        let detectors =
+            [Detector::new("LONG", r"12345").unwrap(), Detector::new("SHORT", r"123").unwrap()];
+
+        let tokenized = tokenize_entities(&mut store, &generator, &detectors, "id 12345 end")
+            .await
+            .unwrap();
+
+        assert!(tokenized.starts_with("id LONG_v0."));
+        assert!(tokenized.ends_with(" end"));
+    }
+}